@@ -29,6 +29,48 @@ fn main() {
         }
     }
 
+    match Command::new("glslc")
+        .args(&["shaders/particle.comp", "-o", "shaders/comp.spv"])
+        .status()
+    {
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+        Ok(status) => {
+            println!("{}", status);
+        }
+    }
+
+    match Command::new("glslc")
+        .args(&["shaders/point.vert", "-o", "shaders/point.vert.spv"])
+        .status()
+    {
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+        Ok(status) => {
+            println!("{}", status);
+        }
+    }
+
+    match Command::new("glslc")
+        .args(&["shaders/point.frag", "-o", "shaders/point.frag.spv"])
+        .status()
+    {
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+        Ok(status) => {
+            println!("{}", status);
+        }
+    }
+
     println!("cargo::rerun-if-changed=shaders/shader.vert");
     println!("cargo::rerun-if-changed=shaders/shader.frag");
+    println!("cargo::rerun-if-changed=shaders/particle.comp");
+    println!("cargo::rerun-if-changed=shaders/point.vert");
+    println!("cargo::rerun-if-changed=shaders/point.frag");
 }
@@ -55,10 +55,12 @@ impl Engine {
                     // Destroy our Vulkan app.
                     WindowEvent::CloseRequested => {
                         elwt.exit();
-                        unsafe { 
-                            self.renderer.destroy(); 
+                        unsafe {
+                            self.renderer.destroy();
                         }
                     }
+                    // Recreate the swapchain on the next frame.
+                    WindowEvent::Resized(_) => self.renderer.resize(),
                     _ => {}
                 }
                 _ => {}
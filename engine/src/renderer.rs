@@ -20,10 +20,15 @@ impl Renderer {
 
     /// Renders a frame for our Vulkan app.
     pub unsafe fn render(&mut self, window: &Window) -> Result<()> {
-        self.vk_renderer.render()?;
+        self.vk_renderer.render(window)?;
         Ok(())
     }
 
+    /// Marks the swapchain for recreation after the window was resized.
+    pub fn resize(&mut self) {
+        self.vk_renderer.resize();
+    }
+
     /// Destroys our Vulkan app.
     pub unsafe fn destroy(&mut self) {
         self.vk_renderer.destroy();
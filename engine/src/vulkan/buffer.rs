@@ -0,0 +1,331 @@
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+
+use anyhow::{anyhow, Ok, Result};
+use vulkanalia::vk::{self, DeviceV1_0, HasBuilder, InstanceV1_0};
+
+use super::{
+    context::VulkanContext,
+    device::{QueueFamilyIndices, VulkanDevice},
+    instance::VulkanInstance,
+};
+
+/// A single vertex fed into the graphics pipeline.
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+    pub pos: [f32; 2],
+    pub color: [f32; 3],
+}
+
+impl Vertex {
+    pub const fn new(pos: [f32; 2], color: [f32; 3]) -> Self {
+        Self { pos, color }
+    }
+
+    pub fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Vertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()
+    }
+
+    pub fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 2] {
+        let pos = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0)
+            .build();
+
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32B32_SFLOAT)
+            .offset(size_of::<[f32; 2]>() as u32)
+            .build();
+
+        [pos, color]
+    }
+}
+
+pub(super) const VERTICES: &[Vertex] = &[
+    Vertex::new([-0.5, -0.5], [1.0, 0.0, 0.0]),
+    Vertex::new([0.5, -0.5], [0.0, 1.0, 0.0]),
+    Vertex::new([0.5, 0.5], [0.0, 0.0, 1.0]),
+    Vertex::new([-0.5, 0.5], [1.0, 1.0, 1.0]),
+];
+
+pub(super) const INDICES: &[u32] = &[0, 1, 2, 2, 3, 0];
+
+/// Finds a memory type whose `property_flags` satisfy `properties` and whose bit is set in
+/// `requirements.memory_type_bits`.
+pub unsafe fn get_memory_type_index(
+    instance: &VulkanInstance,
+    context: &VulkanContext,
+    properties: vk::MemoryPropertyFlags,
+    requirements: vk::MemoryRequirements,
+) -> Result<u32> {
+    let memory = instance
+        .vk_instance
+        .get_physical_device_memory_properties(context.physical_device);
+
+    (0..memory.memory_type_count)
+        .find(|i| {
+            let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
+            let memory_type = memory.memory_types[*i as usize];
+            suitable && memory_type.property_flags.contains(properties)
+        })
+        .ok_or_else(|| anyhow!("Failed to find suitable memory type."))
+}
+
+/// Allocates a `vk::Buffer` backed by `vk::DeviceMemory` satisfying `properties`.
+pub unsafe fn create_buffer(
+    instance: &VulkanInstance,
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    size: vk::DeviceSize,
+    usage: vk::BufferUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    create_buffer_with_sharing(instance, device, context, size, usage, properties, &[])
+}
+
+/// Allocates a `vk::Buffer` backed by `vk::DeviceMemory` satisfying `properties`. When
+/// `concurrent_queue_families` names more than one distinct family, the buffer is created with
+/// `SharingMode::CONCURRENT` across them instead of `EXCLUSIVE`. Needed for buffers written by
+/// one queue family and read by another (e.g. uploaded on the transfer queue, then bound on the
+/// graphics or compute queue) without an explicit queue-family-ownership-transfer barrier.
+unsafe fn create_buffer_with_sharing(
+    instance: &VulkanInstance,
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    size: vk::DeviceSize,
+    usage: vk::BufferUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+    concurrent_queue_families: &[u32],
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    let buffer_info = vk::BufferCreateInfo::builder().size(size).usage(usage);
+    let buffer_info = if concurrent_queue_families.len() > 1 {
+        buffer_info
+            .sharing_mode(vk::SharingMode::CONCURRENT)
+            .queue_family_indices(concurrent_queue_families)
+    } else {
+        buffer_info.sharing_mode(vk::SharingMode::EXCLUSIVE)
+    };
+
+    let buffer = device.vk_device.create_buffer(&buffer_info, None)?;
+
+    let requirements = device.vk_device.get_buffer_memory_requirements(buffer);
+
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type_index(
+            instance,
+            context,
+            properties,
+            requirements,
+        )?);
+
+    let buffer_memory = device.vk_device.allocate_memory(&memory_info, None)?;
+
+    device
+        .vk_device
+        .bind_buffer_memory(buffer, buffer_memory, 0)?;
+
+    Ok((buffer, buffer_memory))
+}
+
+/// Allocates a transient command buffer from `pool`, runs `f` against it, then submits it to
+/// `queue` and waits for it to complete before freeing it.
+unsafe fn execute_commands_on<F>(
+    device: &VulkanDevice,
+    pool: vk::CommandPool,
+    queue: vk::Queue,
+    f: F,
+) -> Result<()>
+where
+    F: FnOnce(vk::CommandBuffer),
+{
+    let info = vk::CommandBufferAllocateInfo::builder()
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_pool(pool)
+        .command_buffer_count(1);
+
+    let command_buffer = device.vk_device.allocate_command_buffers(&info)?[0];
+
+    let begin_info =
+        vk::CommandBufferBeginInfo::builder().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+    device
+        .vk_device
+        .begin_command_buffer(command_buffer, &begin_info)?;
+
+    f(command_buffer);
+
+    device.vk_device.end_command_buffer(command_buffer)?;
+
+    let command_buffers = &[command_buffer];
+    let submit_info = vk::SubmitInfo::builder().command_buffers(command_buffers);
+
+    device
+        .vk_device
+        .queue_submit(queue, &[submit_info], vk::Fence::null())?;
+    device.vk_device.queue_wait_idle(queue)?;
+
+    device.vk_device.free_command_buffers(pool, command_buffers);
+
+    Ok(())
+}
+
+/// Runs `f` against a transient command buffer submitted to the graphics queue, via
+/// `context.command_pool`. Used for one-off commands (e.g. image layout transitions) that must
+/// run on a queue supporting graphics.
+pub(super) unsafe fn execute_one_time_commands<F>(
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    f: F,
+) -> Result<()>
+where
+    F: FnOnce(vk::CommandBuffer),
+{
+    execute_commands_on(device, context.command_pool, context.graphics_queue, f)
+}
+
+/// Runs `f` against a transient command buffer submitted to `context.transfer_queue`, via
+/// `context.transfer_command_pool`. Used for plain buffer-to-buffer copies, which can run
+/// concurrently with rendering on a dedicated DMA queue.
+unsafe fn execute_transfer_commands<F>(
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    f: F,
+) -> Result<()>
+where
+    F: FnOnce(vk::CommandBuffer),
+{
+    execute_commands_on(
+        device,
+        context.transfer_command_pool,
+        context.transfer_queue,
+        f,
+    )
+}
+
+/// Copies `size` bytes from `source` to `destination` on the transfer queue.
+pub unsafe fn copy_buffer(
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    source: vk::Buffer,
+    destination: vk::Buffer,
+    size: vk::DeviceSize,
+) -> Result<()> {
+    execute_transfer_commands(device, context, |command_buffer| {
+        let regions = vk::BufferCopy::builder().size(size);
+        device
+            .vk_device
+            .cmd_copy_buffer(command_buffer, source, destination, &[regions]);
+    })
+}
+
+/// Uploads `data` into a device-local buffer with the given `usage` via a host-visible staging
+/// buffer that is destroyed once the copy completes.
+pub(super) unsafe fn create_device_local_buffer<T: Copy>(
+    instance: &VulkanInstance,
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    data: &[T],
+    usage: vk::BufferUsageFlags,
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    let size = (size_of::<T>() * data.len()) as u64;
+
+    let (staging_buffer, staging_buffer_memory) = create_buffer(
+        instance,
+        device,
+        context,
+        size,
+        vk::BufferUsageFlags::TRANSFER_SRC,
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    let memory = device
+        .vk_device
+        .map_memory(staging_buffer_memory, 0, size, vk::MemoryMapFlags::empty())?;
+    memcpy(data.as_ptr(), memory.cast(), data.len());
+    device.vk_device.unmap_memory(staging_buffer_memory);
+
+    // This buffer is uploaded to on the transfer queue below, then later read on the graphics
+    // queue (vertex/index buffers) and/or the compute queue (particle storage buffers) with no
+    // ownership-transfer barrier, so it must be shared across those families rather than
+    // EXCLUSIVE to the transfer queue that wrote it.
+    let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
+    let mut concurrent_queue_families = vec![indices.graphics, indices.compute];
+    concurrent_queue_families.extend(indices.transfer);
+    concurrent_queue_families.sort_unstable();
+    concurrent_queue_families.dedup();
+
+    let (buffer, buffer_memory) = create_buffer_with_sharing(
+        instance,
+        device,
+        context,
+        size,
+        vk::BufferUsageFlags::TRANSFER_DST | usage,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        &concurrent_queue_families,
+    )?;
+
+    copy_buffer(device, context, staging_buffer, buffer, size)?;
+
+    device.vk_device.destroy_buffer(staging_buffer, None);
+    device.vk_device.free_memory(staging_buffer_memory, None);
+
+    Ok((buffer, buffer_memory))
+}
+
+/// Uploads `VERTICES` into a device-local `VERTEX_BUFFER` via a host-visible staging buffer
+/// (see [`create_device_local_buffer`]), so geometry never lives in slower host-visible memory.
+#[derive(Debug)]
+pub struct VulkanVertexBuffer;
+
+impl VulkanVertexBuffer {
+    pub unsafe fn create(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let (buffer, buffer_memory) = create_device_local_buffer(
+            instance,
+            device,
+            context,
+            VERTICES,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+        )?;
+
+        context.vertex_buffer = buffer;
+        context.vertex_buffer_memory = buffer_memory;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct VulkanIndexBuffer;
+
+impl VulkanIndexBuffer {
+    pub unsafe fn create(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let (buffer, buffer_memory) = create_device_local_buffer(
+            instance,
+            device,
+            context,
+            INDICES,
+            vk::BufferUsageFlags::INDEX_BUFFER,
+        )?;
+
+        context.index_buffer = buffer;
+        context.index_buffer_memory = buffer_memory;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 use super::{
+    buffer::INDICES,
+    compute::PARTICLE_COUNT,
     context::VulkanContext,
     device::{QueueFamilyIndices, VulkanDevice},
     instance::VulkanInstance,
@@ -18,7 +20,7 @@ impl VulkanCommandBuffer {
         let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
 
         let info = vk::CommandPoolCreateInfo::builder()
-            .flags(vk::CommandPoolCreateFlags::empty())
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
             .queue_family_index(indices.graphics);
 
         context.command_pool = device.vk_device.create_command_pool(&info, None)?;
@@ -26,6 +28,24 @@ impl VulkanCommandBuffer {
         Ok(())
     }
 
+    /// Creates a transient command pool bound to a dedicated transfer queue family when the
+    /// device has one, falling back to the graphics family otherwise.
+    pub unsafe fn create_transfer_command_pool(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
+
+        let info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+            .queue_family_index(indices.transfer.unwrap_or(indices.graphics));
+
+        context.transfer_command_pool = device.vk_device.create_command_pool(&info, None)?;
+
+        Ok(())
+    }
+
     pub unsafe fn create_command_buffers(
         device: &VulkanDevice,
         context: &mut VulkanContext,
@@ -37,48 +57,114 @@ impl VulkanCommandBuffer {
 
         context.command_buffers = device.vk_device.allocate_command_buffers(&allocate_info)?;
 
-        for (i, command_buffer) in context.command_buffers.iter().enumerate() {
-            let info = vk::CommandBufferBeginInfo::builder();
-
-            device
-                .vk_device
-                .begin_command_buffer(*command_buffer, &info)?;
-
-            let render_area = vk::Rect2D::builder()
-                .offset(vk::Offset2D::default())
-                .extent(context.swapchain_extent);
-
-            let color_clear_value = vk::ClearValue {
-                color: vk::ClearColorValue {
-                    float32: [0.0, 0.0, 0.0, 1.0],
-                },
-            };
-
-            let clear_values = &[color_clear_value];
-            let info = vk::RenderPassBeginInfo::builder()
-                .render_pass(context.render_pass)
-                .framebuffer(context.framebuffers[i])
-                .render_area(render_area)
-                .clear_values(clear_values);
-
-            device.vk_device.cmd_begin_render_pass(
-                *command_buffer,
-                &info,
-                vk::SubpassContents::INLINE,
-            );
-
-            device.vk_device.cmd_bind_pipeline(
-                *command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
-                context.pipeline,
-            );
-
-            device.vk_device.cmd_draw(*command_buffer, 3, 1, 0, 0);
-            device.vk_device.cmd_end_render_pass(*command_buffer);
-
-            device.vk_device.end_command_buffer(*command_buffer)?;
+        for i in 0..context.command_buffers.len() {
+            VulkanCommandBuffer::record_command_buffer(device, context, i, 0)?;
         }
 
         Ok(())
     }
+
+    /// Re-records the command buffer for `image_index`, drawing the textured quad followed by
+    /// the particles currently held in `particle_buffers[particle_buffer]`.
+    pub unsafe fn record_command_buffer(
+        device: &VulkanDevice,
+        context: &VulkanContext,
+        image_index: usize,
+        particle_buffer: usize,
+    ) -> Result<()> {
+        let command_buffer = context.command_buffers[image_index];
+
+        device.vk_device.reset_command_buffer(
+            command_buffer,
+            vk::CommandBufferResetFlags::empty(),
+        )?;
+
+        let info = vk::CommandBufferBeginInfo::builder();
+        device
+            .vk_device
+            .begin_command_buffer(command_buffer, &info)?;
+
+        let render_area = vk::Rect2D::builder()
+            .offset(vk::Offset2D::default())
+            .extent(context.swapchain_extent);
+
+        let color_clear_value = vk::ClearValue {
+            color: vk::ClearColorValue {
+                float32: [0.0, 0.0, 0.0, 1.0],
+            },
+        };
+
+        let depth_clear_value = vk::ClearValue {
+            depth_stencil: vk::ClearDepthStencilValue {
+                depth: 1.0,
+                stencil: 0,
+            },
+        };
+
+        let clear_values = &[color_clear_value, depth_clear_value];
+        let info = vk::RenderPassBeginInfo::builder()
+            .render_pass(context.render_pass)
+            .framebuffer(context.framebuffers[image_index])
+            .render_area(render_area)
+            .clear_values(clear_values);
+
+        device.vk_device.cmd_begin_render_pass(
+            command_buffer,
+            &info,
+            vk::SubpassContents::INLINE,
+        );
+
+        device.vk_device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            context.pipeline,
+        );
+
+        device.vk_device.cmd_bind_vertex_buffers(
+            command_buffer,
+            0,
+            &[context.vertex_buffer],
+            &[0],
+        );
+        device.vk_device.cmd_bind_index_buffer(
+            command_buffer,
+            context.index_buffer,
+            0,
+            vk::IndexType::UINT32,
+        );
+
+        device.vk_device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            context.pipeline_layout,
+            0,
+            &[context.descriptor_sets[image_index]],
+            &[],
+        );
+
+        device
+            .vk_device
+            .cmd_draw_indexed(command_buffer, INDICES.len() as u32, 1, 0, 0, 0);
+
+        device.vk_device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            context.particle_pipeline,
+        );
+        device.vk_device.cmd_bind_vertex_buffers(
+            command_buffer,
+            0,
+            &[context.particle_buffers[particle_buffer]],
+            &[0],
+        );
+        device
+            .vk_device
+            .cmd_draw(command_buffer, PARTICLE_COUNT as u32, 1, 0, 0);
+
+        device.vk_device.cmd_end_render_pass(command_buffer);
+
+        device.vk_device.end_command_buffer(command_buffer)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,302 @@
+use std::f32::consts::TAU;
+use std::mem::size_of;
+
+use anyhow::{Ok, Result};
+use vulkanalia::bytecode::Bytecode;
+use vulkanalia::vk::{self, DeviceV1_0, HasBuilder};
+
+use super::buffer::create_device_local_buffer;
+use super::{
+    constants,
+    context::VulkanContext,
+    device::{QueueFamilyIndices, VulkanDevice},
+    instance::VulkanInstance,
+};
+
+pub const PARTICLE_COUNT: usize = 4096;
+
+/// A single simulated particle; also bound directly as a point-list vertex when rendered.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Particle {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub color: [f32; 4],
+}
+
+impl Particle {
+    pub fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Particle>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()
+    }
+
+    pub fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 2] {
+        let position = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0)
+            .build();
+
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32B32A32_SFLOAT)
+            .offset(size_of::<[f32; 2]>() as u32 * 2)
+            .build();
+
+        [position, color]
+    }
+}
+
+/// Seeds the simulation with particles arranged on a ring, each drifting outward.
+fn initial_particles() -> Vec<Particle> {
+    (0..PARTICLE_COUNT)
+        .map(|i| {
+            let angle = (i as f32 / PARTICLE_COUNT as f32) * TAU;
+            let radius = 0.25;
+            Particle {
+                position: [radius * angle.cos(), radius * angle.sin()],
+                velocity: [angle.cos() * 0.05, angle.sin() * 0.05],
+                color: [1.0, 1.0, 1.0, 1.0],
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct VulkanComputePipeline;
+
+impl VulkanComputePipeline {
+    /// Allocates the ping-pong particle storage buffers and builds the compute descriptor
+    /// layout, pipeline layout, and pipeline.
+    pub unsafe fn create(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let particles = initial_particles();
+        for _ in 0..2 {
+            let (buffer, memory) = create_device_local_buffer(
+                instance,
+                device,
+                context,
+                &particles,
+                vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::VERTEX_BUFFER,
+            )?;
+            context.particle_buffers.push(buffer);
+            context.particle_buffers_memory.push(memory);
+        }
+
+        let previous_binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE);
+
+        let current_binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(1)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE);
+
+        let bindings = &[previous_binding, current_binding];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+        context.compute_descriptor_set_layout =
+            device.vk_device.create_descriptor_set_layout(&layout_info, None)?;
+
+        let set_layouts = &[context.compute_descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::builder().set_layouts(set_layouts);
+        context.compute_pipeline_layout = device
+            .vk_device
+            .create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let comp = include_bytes!("../../../shaders/comp.spv");
+        let bytecode = Bytecode::new(&comp[..]).unwrap();
+        let module_info = vk::ShaderModuleCreateInfo::builder()
+            .code_size(bytecode.code_size())
+            .code(bytecode.code());
+        let module = device.vk_device.create_shader_module(&module_info, None)?;
+
+        let stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(module)
+            .name(b"main\0");
+
+        let pipeline_info = vk::ComputePipelineCreateInfo::builder()
+            .stage(stage)
+            .layout(context.compute_pipeline_layout);
+
+        context.compute_pipeline = device
+            .vk_device
+            .create_compute_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)?
+            .0[0];
+
+        device.vk_device.destroy_shader_module(module, None);
+
+        Self::create_descriptor_sets(device, context)?;
+
+        Ok(())
+    }
+
+    /// Allocates two descriptor sets, one per ping-pong direction: set `i` reads
+    /// `particle_buffers[i]` as the previous-frame state and writes `particle_buffers[i ^ 1]`.
+    unsafe fn create_descriptor_sets(device: &VulkanDevice, context: &mut VulkanContext) -> Result<()> {
+        let pool_size = vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(4);
+
+        let pool_sizes = &[pool_size];
+        let pool_info = vk::DescriptorPoolCreateInfo::builder()
+            .pool_sizes(pool_sizes)
+            .max_sets(2);
+
+        context.compute_descriptor_pool = device.vk_device.create_descriptor_pool(&pool_info, None)?;
+
+        let layouts = vec![context.compute_descriptor_set_layout; 2];
+        let alloc_info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(context.compute_descriptor_pool)
+            .set_layouts(&layouts);
+
+        context.compute_descriptor_sets = device.vk_device.allocate_descriptor_sets(&alloc_info)?;
+
+        let buffer_size = (size_of::<Particle>() * PARTICLE_COUNT) as u64;
+
+        for i in 0..2 {
+            let previous_info = vk::DescriptorBufferInfo::builder()
+                .buffer(context.particle_buffers[i])
+                .offset(0)
+                .range(buffer_size);
+            let current_info = vk::DescriptorBufferInfo::builder()
+                .buffer(context.particle_buffers[i ^ 1])
+                .offset(0)
+                .range(buffer_size);
+
+            let previous_buffer_info = &[previous_info];
+            let previous_write = vk::WriteDescriptorSet::builder()
+                .dst_set(context.compute_descriptor_sets[i])
+                .dst_binding(0)
+                .dst_array_element(0)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(previous_buffer_info);
+
+            let current_buffer_info = &[current_info];
+            let current_write = vk::WriteDescriptorSet::builder()
+                .dst_set(context.compute_descriptor_sets[i])
+                .dst_binding(1)
+                .dst_array_element(0)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(current_buffer_info);
+
+            device.vk_device.update_descriptor_sets(
+                &[previous_write, current_write],
+                &[] as &[vk::CopyDescriptorSet],
+            );
+        }
+
+        Ok(())
+    }
+
+    pub unsafe fn create_command_pool(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
+
+        let info = vk::CommandPoolCreateInfo::builder()
+            .queue_family_index(indices.compute)
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
+        context.compute_command_pool = device.vk_device.create_command_pool(&info, None)?;
+
+        Ok(())
+    }
+
+    pub unsafe fn create_command_buffers(device: &VulkanDevice, context: &mut VulkanContext) -> Result<()> {
+        let info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(context.compute_command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(constants::MAX_FRAMES_IN_FLIGHT as u32);
+
+        context.compute_command_buffers = device.vk_device.allocate_command_buffers(&info)?;
+
+        Ok(())
+    }
+
+    /// Records and submits the dispatch that advances particles for `frame`, reading
+    /// `particle_buffers[frame % 2]` and writing `particle_buffers[(frame + 1) % 2]`, then
+    /// signals `compute_finished_semaphores[frame]` so the graphics submit can order itself
+    /// after the write.
+    pub unsafe fn submit(device: &VulkanDevice, context: &VulkanContext, frame: usize) -> Result<()> {
+        let set = frame % 2;
+        let command_buffer = context.compute_command_buffers[frame];
+
+        device
+            .vk_device
+            .reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())?;
+
+        let begin_info = vk::CommandBufferBeginInfo::builder();
+        device.vk_device.begin_command_buffer(command_buffer, &begin_info)?;
+
+        device.vk_device.cmd_bind_pipeline(
+            command_buffer,
+            vk::PipelineBindPoint::COMPUTE,
+            context.compute_pipeline,
+        );
+        device.vk_device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::COMPUTE,
+            context.compute_pipeline_layout,
+            0,
+            &[context.compute_descriptor_sets[set]],
+            &[],
+        );
+        device.vk_device.cmd_dispatch(
+            command_buffer,
+            (PARTICLE_COUNT as u32 + 255) / 256,
+            1,
+            1,
+        );
+
+        device.vk_device.end_command_buffer(command_buffer)?;
+
+        let command_buffers = &[command_buffer];
+        let signal_semaphores = &[context.compute_finished_semaphores[frame]];
+        let submit_info = vk::SubmitInfo::builder()
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+
+        device
+            .vk_device
+            .queue_submit(context.compute_queue, &[submit_info], vk::Fence::null())?;
+
+        Ok(())
+    }
+
+    pub unsafe fn destroy(device: &VulkanDevice, context: &mut VulkanContext) {
+        device
+            .vk_device
+            .destroy_descriptor_pool(context.compute_descriptor_pool, None);
+        device
+            .vk_device
+            .destroy_descriptor_set_layout(context.compute_descriptor_set_layout, None);
+        device.vk_device.destroy_pipeline(context.compute_pipeline, None);
+        device
+            .vk_device
+            .destroy_pipeline_layout(context.compute_pipeline_layout, None);
+        device
+            .vk_device
+            .destroy_command_pool(context.compute_command_pool, None);
+        context
+            .particle_buffers
+            .iter()
+            .for_each(|b| device.vk_device.destroy_buffer(*b, None));
+        context
+            .particle_buffers_memory
+            .iter()
+            .for_each(|m| device.vk_device.free_memory(*m, None));
+    }
+}
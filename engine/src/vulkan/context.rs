@@ -11,6 +11,8 @@ pub struct VulkanContext {
     pub physical_device: vk::PhysicalDevice,
     pub graphics_queue: vk::Queue,
     pub present_queue: vk::Queue,
+    pub transfer_queue: vk::Queue,
+    pub transfer_command_pool: vk::CommandPool,
     // Swapchain attributes
     pub swapchain: vk::SwapchainKHR,
     pub swapchain_images: Vec<vk::Image>,
@@ -26,7 +28,46 @@ pub struct VulkanContext {
     // Commands buffer
     pub command_pool: vk::CommandPool,
     pub command_buffers: Vec<vk::CommandBuffer>,
-    // Semaphore
-    pub image_available_semaphore: vk::Semaphore,
-    pub render_finished_semaphore: vk::Semaphore,
+    // Vertex buffer
+    pub vertex_buffer: vk::Buffer,
+    pub vertex_buffer_memory: vk::DeviceMemory,
+    // Index buffer
+    pub index_buffer: vk::Buffer,
+    pub index_buffer_memory: vk::DeviceMemory,
+    // Descriptors
+    pub descriptor_set_layout: vk::DescriptorSetLayout,
+    pub descriptor_pool: vk::DescriptorPool,
+    pub descriptor_sets: Vec<vk::DescriptorSet>,
+    pub uniform_buffers: Vec<vk::Buffer>,
+    pub uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    // Texture
+    pub texture_image: vk::Image,
+    pub texture_image_memory: vk::DeviceMemory,
+    pub texture_image_view: vk::ImageView,
+    pub texture_sampler: vk::Sampler,
+    // Depth
+    pub depth_format: vk::Format,
+    pub depth_image: vk::Image,
+    pub depth_image_memory: vk::DeviceMemory,
+    pub depth_image_view: vk::ImageView,
+    // Sync objects, one slot per frame-in-flight except `images_in_flight`, which is indexed
+    // by swapchain image and records which frame's fence currently owns it.
+    pub image_available_semaphores: Vec<vk::Semaphore>,
+    pub render_finished_semaphores: Vec<vk::Semaphore>,
+    pub in_flight_fences: Vec<vk::Fence>,
+    pub images_in_flight: Vec<vk::Fence>,
+    // Compute
+    pub compute_queue: vk::Queue,
+    pub compute_descriptor_set_layout: vk::DescriptorSetLayout,
+    pub compute_pipeline_layout: vk::PipelineLayout,
+    pub compute_pipeline: vk::Pipeline,
+    pub compute_descriptor_pool: vk::DescriptorPool,
+    pub compute_descriptor_sets: Vec<vk::DescriptorSet>,
+    pub compute_command_pool: vk::CommandPool,
+    pub compute_command_buffers: Vec<vk::CommandBuffer>,
+    pub compute_finished_semaphores: Vec<vk::Semaphore>,
+    pub particle_buffers: Vec<vk::Buffer>,
+    pub particle_buffers_memory: Vec<vk::DeviceMemory>,
+    pub particle_pipeline_layout: vk::PipelineLayout,
+    pub particle_pipeline: vk::Pipeline,
 }
@@ -0,0 +1,191 @@
+use std::mem::size_of;
+use std::ptr::copy_nonoverlapping as memcpy;
+use std::time::Instant;
+
+use anyhow::{Ok, Result};
+use cgmath::{point3, vec3, Deg};
+use vulkanalia::vk::{self, DeviceV1_0, HasBuilder};
+
+use super::buffer::create_buffer;
+use super::{context::VulkanContext, device::VulkanDevice, instance::VulkanInstance};
+
+type Mat4 = cgmath::Matrix4<f32>;
+
+/// The per-frame camera/model transform handed to the vertex shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct UniformBufferObject {
+    pub model: Mat4,
+    pub view: Mat4,
+    pub proj: Mat4,
+}
+
+#[derive(Debug)]
+pub struct VulkanDescriptor;
+
+impl VulkanDescriptor {
+    pub unsafe fn create_descriptor_set_layout(
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let ubo_binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::VERTEX);
+
+        let sampler_binding = vk::DescriptorSetLayoutBinding::builder()
+            .binding(1)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+        let bindings = &[ubo_binding, sampler_binding];
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(bindings);
+
+        context.descriptor_set_layout = device.vk_device.create_descriptor_set_layout(&info, None)?;
+
+        Ok(())
+    }
+
+    pub unsafe fn create_uniform_buffers(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        context.uniform_buffers.clear();
+        context.uniform_buffers_memory.clear();
+
+        for _ in 0..context.swapchain_images.len() {
+            let (uniform_buffer, uniform_buffer_memory) = create_buffer(
+                instance,
+                device,
+                context,
+                size_of::<UniformBufferObject>() as u64,
+                vk::BufferUsageFlags::UNIFORM_BUFFER,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+
+            context.uniform_buffers.push(uniform_buffer);
+            context.uniform_buffers_memory.push(uniform_buffer_memory);
+        }
+
+        Ok(())
+    }
+
+    pub unsafe fn create_descriptor_pool(
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let ubo_pool_size = vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_count(context.swapchain_images.len() as u32);
+
+        let sampler_pool_size = vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(context.swapchain_images.len() as u32);
+
+        let pool_sizes = &[ubo_pool_size, sampler_pool_size];
+        let info = vk::DescriptorPoolCreateInfo::builder()
+            .pool_sizes(pool_sizes)
+            .max_sets(context.swapchain_images.len() as u32);
+
+        context.descriptor_pool = device.vk_device.create_descriptor_pool(&info, None)?;
+
+        Ok(())
+    }
+
+    pub unsafe fn create_descriptor_sets(
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let layouts = vec![context.descriptor_set_layout; context.swapchain_images.len()];
+        let info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(context.descriptor_pool)
+            .set_layouts(&layouts);
+
+        context.descriptor_sets = device.vk_device.allocate_descriptor_sets(&info)?;
+
+        for i in 0..context.swapchain_images.len() {
+            let info = vk::DescriptorBufferInfo::builder()
+                .buffer(context.uniform_buffers[i])
+                .offset(0)
+                .range(size_of::<UniformBufferObject>() as u64);
+
+            let buffer_info = &[info];
+            let ubo_write = vk::WriteDescriptorSet::builder()
+                .dst_set(context.descriptor_sets[i])
+                .dst_binding(0)
+                .dst_array_element(0)
+                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                .buffer_info(buffer_info);
+
+            let info = vk::DescriptorImageInfo::builder()
+                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .image_view(context.texture_image_view)
+                .sampler(context.texture_sampler);
+
+            let image_info = &[info];
+            let sampler_write = vk::WriteDescriptorSet::builder()
+                .dst_set(context.descriptor_sets[i])
+                .dst_binding(1)
+                .dst_array_element(0)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .image_info(image_info);
+
+            device.vk_device.update_descriptor_sets(
+                &[ubo_write, sampler_write],
+                &[] as &[vk::CopyDescriptorSet],
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Computes a rotating model matrix and a projection matching `swapchain_extent`'s aspect
+    /// ratio, then copies the resulting UBO into the uniform buffer for `image_index`.
+    pub unsafe fn update_uniform_buffer(
+        device: &VulkanDevice,
+        context: &VulkanContext,
+        image_index: usize,
+        start: Instant,
+    ) -> Result<()> {
+        let time = start.elapsed().as_secs_f32();
+
+        let model = Mat4::from_axis_angle(vec3(0.0, 0.0, 1.0), Deg(90.0) * time);
+
+        let view = Mat4::look_at_rh(
+            point3(2.0, 2.0, 2.0),
+            point3(0.0, 0.0, 0.0),
+            vec3(0.0, 0.0, 1.0),
+        );
+
+        let extent = context.swapchain_extent;
+        let mut proj = cgmath::perspective(
+            Deg(45.0),
+            extent.width as f32 / extent.height as f32,
+            0.1,
+            10.0,
+        );
+
+        // Vulkan's clip space has an inverted Y compared to OpenGL's.
+        proj[1][1] *= -1.0;
+
+        let ubo = UniformBufferObject { model, view, proj };
+
+        let memory = device.vk_device.map_memory(
+            context.uniform_buffers_memory[image_index],
+            0,
+            size_of::<UniformBufferObject>() as u64,
+            vk::MemoryMapFlags::empty(),
+        )?;
+
+        memcpy(&ubo, memory.cast(), 1);
+
+        device
+            .vk_device
+            .unmap_memory(context.uniform_buffers_memory[image_index]);
+
+        Ok(())
+    }
+}
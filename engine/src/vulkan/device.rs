@@ -4,7 +4,10 @@ use anyhow::{anyhow, Ok, Result};
 use log::*;
 use thiserror::Error;
 use vulkanalia::{
-    vk::{self, DeviceV1_0, HasBuilder, InstanceV1_0, KhrSurfaceExtension, KhrSwapchainExtension},
+    vk::{
+        self, DeviceV1_0, ExtDebugUtilsExtension, HasBuilder, InstanceV1_0, KhrSurfaceExtension,
+        KhrSwapchainExtension,
+    },
     Device, Entry,
 };
 
@@ -22,10 +25,30 @@ pub struct VulkanDevice {
 pub struct SuitabilityError(pub &'static str);
 
 impl VulkanDevice {
+    /// Scores a suitable device, strongly favoring discrete GPUs and larger image limits, so
+    /// that laptops with both an integrated and a discrete adapter pick the discrete one.
+    unsafe fn score_physical_device(
+        instance: &VulkanInstance,
+        physical_device: vk::PhysicalDevice,
+    ) -> u32 {
+        let properties = instance
+            .vk_instance
+            .get_physical_device_properties(physical_device);
+
+        let mut score = properties.limits.max_image_dimension2_d;
+        if properties.device_type == vk::PhysicalDeviceType::DISCRETE_GPU {
+            score += 100_000;
+        }
+
+        score
+    }
+
     unsafe fn pick_physical_device(
         instance: &VulkanInstance,
         context: &mut VulkanContext,
     ) -> Result<()> {
+        let mut best: Option<(u32, vk::PhysicalDevice)> = None;
+
         for physical_device in instance.vk_instance.enumerate_physical_devices()? {
             let properties = instance
                 .vk_instance
@@ -38,13 +61,25 @@ impl VulkanDevice {
                     "Skipping physical device (`{}`): {}",
                     properties.device_name, error
                 );
-            } else {
-                info!("Selected physical device (`{}`).", properties.device_name);
-                context.physical_device = physical_device;
-                return Ok(());
+                continue;
+            }
+
+            let score = VulkanDevice::score_physical_device(instance, physical_device);
+            if best.map_or(true, |(best_score, _)| score > best_score) {
+                best = Some((score, physical_device));
             }
         }
-        Err(anyhow!("Failed to find suitable physical device."))
+
+        let (_, physical_device) =
+            best.ok_or_else(|| anyhow!("Failed to find suitable physical device."))?;
+
+        let properties = instance
+            .vk_instance
+            .get_physical_device_properties(physical_device);
+        info!("Selected physical device (`{}`).", properties.device_name);
+        context.physical_device = physical_device;
+
+        Ok(())
     }
 
     unsafe fn check_physical_device(
@@ -116,13 +151,21 @@ impl VulkanDevice {
             extensions.push(vk::KHR_PORTABILITY_SUBSET_EXTENSION.name.as_ptr());
         }
 
-        let features = vk::PhysicalDeviceFeatures::builder();
+        // Only request optional features the chosen device actually advertises.
+        let supported_features = instance
+            .vk_instance
+            .get_physical_device_features(context.physical_device);
+        let features = vk::PhysicalDeviceFeatures::builder()
+            .sampler_anisotropy(supported_features.sampler_anisotropy == vk::TRUE)
+            .fill_mode_non_solid(supported_features.fill_mode_non_solid == vk::TRUE);
 
         let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
 
         let mut unique_indices = HashSet::new();
         unique_indices.insert(indices.graphics);
         unique_indices.insert(indices.present);
+        unique_indices.insert(indices.compute);
+        unique_indices.insert(indices.transfer.unwrap_or(indices.graphics));
 
         let queue_priorities = &[1.0];
         let queue_infos = unique_indices
@@ -146,17 +189,25 @@ impl VulkanDevice {
 
         context.graphics_queue = device.get_device_queue(indices.graphics, 0);
         context.present_queue = device.get_device_queue(indices.present, 0);
+        context.compute_queue = device.get_device_queue(indices.compute, 0);
+        context.transfer_queue =
+            device.get_device_queue(indices.transfer.unwrap_or(indices.graphics), 0);
 
         Ok(VulkanDevice { vk_device: device })
     }
 
-    pub unsafe fn destroy(&mut self, context: &mut VulkanContext) {
+    pub unsafe fn destroy(&mut self, instance: &VulkanInstance, context: &mut VulkanContext) {
         context
             .swapchain_image_views
             .iter()
             .for_each(|v| self.vk_device.destroy_image_view(*v, None));
         self.vk_device
             .destroy_swapchain_khr(context.swapchain, None);
+        if constants::VALIDATION_ENABLED {
+            instance
+                .vk_instance
+                .destroy_debug_utils_messenger_ext(context.messenger, None);
+        }
         self.vk_device.destroy_device(None);
     }
 }
@@ -165,6 +216,10 @@ impl VulkanDevice {
 pub struct QueueFamilyIndices {
     pub graphics: u32,
     pub present: u32,
+    pub compute: u32,
+    /// A queue family supporting `TRANSFER` but not `GRAPHICS` (a dedicated DMA queue), when the
+    /// device exposes one. Callers fall back to `graphics` otherwise.
+    pub transfer: Option<u32>,
 }
 
 impl QueueFamilyIndices {
@@ -182,6 +237,19 @@ impl QueueFamilyIndices {
             .position(|p| p.queue_flags.contains(vk::QueueFlags::GRAPHICS))
             .map(|i| i as u32);
 
+        let compute = properties
+            .iter()
+            .position(|p| p.queue_flags.contains(vk::QueueFlags::COMPUTE))
+            .map(|i| i as u32);
+
+        let transfer = properties
+            .iter()
+            .position(|p| {
+                p.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                    && !p.queue_flags.contains(vk::QueueFlags::GRAPHICS)
+            })
+            .map(|i| i as u32);
+
         let mut present = None;
         for (index, properties) in properties.iter().enumerate() {
             if instance
@@ -197,8 +265,13 @@ impl QueueFamilyIndices {
             }
         }
 
-        if let (Some(graphics), Some(present)) = (graphics, present) {
-            Ok(Self { graphics, present })
+        if let (Some(graphics), Some(present), Some(compute)) = (graphics, present, compute) {
+            Ok(Self {
+                graphics,
+                present,
+                compute,
+                transfer,
+            })
         } else {
             Err(anyhow!(SuitabilityError(
                 "Missing required queue families."
@@ -10,7 +10,7 @@ impl VulkanFramebuffer {
             .swapchain_image_views
             .iter()
             .map(|i| {
-                let attachments = &[*i];
+                let attachments = &[*i, context.depth_image_view];
                 let create_info = vk::FramebufferCreateInfo::builder()
                     .render_pass(context.render_pass)
                     .attachments(attachments)
@@ -1,9 +1,343 @@
-use anyhow::Result;
-use vulkanalia::vk::{self, Handle, KhrSwapchainExtension};
+use std::ptr::copy_nonoverlapping as memcpy;
 
-use super::{context::VulkanContext, device::VulkanDevice};
+use anyhow::{anyhow, Ok, Result};
+use vulkanalia::vk::{self, DeviceV1_0, HasBuilder, InstanceV1_0};
+
+use super::buffer::create_buffer;
+use super::{context::VulkanContext, device::VulkanDevice, instance::VulkanInstance};
+
+/// Embedded so the renderer doesn't depend on a working directory containing `resources/` at
+/// runtime, matching how shader bytecode is embedded via `include_bytes!` elsewhere.
+const TEXTURE_BYTES: &[u8] = include_bytes!("../../../resources/texture.png");
+
+/// Allocates a `vk::Image` backed by `vk::DeviceMemory` satisfying `properties`.
+pub unsafe fn create_image(
+    instance: &VulkanInstance,
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    width: u32,
+    height: u32,
+    format: vk::Format,
+    tiling: vk::ImageTiling,
+    usage: vk::ImageUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<(vk::Image, vk::DeviceMemory)> {
+    let info = vk::ImageCreateInfo::builder()
+        .image_type(vk::ImageType::_2D)
+        .extent(vk::Extent3D {
+            width,
+            height,
+            depth: 1,
+        })
+        .mip_levels(1)
+        .array_layers(1)
+        .format(format)
+        .tiling(tiling)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .usage(usage)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        .samples(vk::SampleCountFlags::_1);
+
+    let image = device.vk_device.create_image(&info, None)?;
+
+    let requirements = device.vk_device.get_image_memory_requirements(image);
+
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(super::buffer::get_memory_type_index(
+            instance,
+            context,
+            properties,
+            requirements,
+        )?);
+
+    let image_memory = device.vk_device.allocate_memory(&memory_info, None)?;
+    device.vk_device.bind_image_memory(image, image_memory, 0)?;
+
+    Ok((image, image_memory))
+}
+
+pub unsafe fn create_image_view(
+    device: &VulkanDevice,
+    image: vk::Image,
+    format: vk::Format,
+    aspects: vk::ImageAspectFlags,
+) -> Result<vk::ImageView> {
+    let subresource_range = vk::ImageSubresourceRange::builder()
+        .aspect_mask(aspects)
+        .base_mip_level(0)
+        .level_count(1)
+        .base_array_layer(0)
+        .layer_count(1);
+
+    let info = vk::ImageViewCreateInfo::builder()
+        .image(image)
+        .view_type(vk::ImageViewType::_2D)
+        .format(format)
+        .subresource_range(subresource_range);
+
+    Ok(device.vk_device.create_image_view(&info, None)?)
+}
+
+/// Probes `candidates` in order and returns the first whose `tiling`-appropriate features
+/// contain `features`.
+pub unsafe fn find_supported_format(
+    instance: &VulkanInstance,
+    context: &VulkanContext,
+    candidates: &[vk::Format],
+    tiling: vk::ImageTiling,
+    features: vk::FormatFeatureFlags,
+) -> Result<vk::Format> {
+    candidates
+        .iter()
+        .cloned()
+        .find(|f| {
+            let properties = instance
+                .vk_instance
+                .get_physical_device_format_properties(context.physical_device, *f);
+
+            match tiling {
+                vk::ImageTiling::LINEAR => properties.linear_tiling_features.contains(features),
+                vk::ImageTiling::OPTIMAL => properties.optimal_tiling_features.contains(features),
+                _ => false,
+            }
+        })
+        .ok_or_else(|| anyhow!("Failed to find supported format."))
+}
+
+/// Picks the first depth/stencil format the device supports, preferring `D32_SFLOAT`.
+pub unsafe fn find_depth_format(
+    instance: &VulkanInstance,
+    context: &VulkanContext,
+) -> Result<vk::Format> {
+    find_supported_format(
+        instance,
+        context,
+        &[
+            vk::Format::D32_SFLOAT,
+            vk::Format::D32_SFLOAT_S8_UINT,
+            vk::Format::D24_UNORM_S8_UINT,
+        ],
+        vk::ImageTiling::OPTIMAL,
+        vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+    )
+}
+
+/// Transitions `image` between layouts via `cmd_pipeline_barrier`, picking access masks and
+/// pipeline stages appropriate for the undefined -> transfer -> shader-read upload path.
+pub unsafe fn transition_image_layout(
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    image: vk::Image,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+) -> Result<()> {
+    let (src_access_mask, dst_access_mask, src_stage, dst_stage) = match (old_layout, new_layout) {
+        (vk::ImageLayout::UNDEFINED, vk::ImageLayout::TRANSFER_DST_OPTIMAL) => (
+            vk::AccessFlags::empty(),
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::TRANSFER,
+        ),
+        (vk::ImageLayout::TRANSFER_DST_OPTIMAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL) => (
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::AccessFlags::SHADER_READ,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+        ),
+        _ => return Err(anyhow!("Unsupported layout transition.")),
+    };
+
+    super::buffer::execute_one_time_commands(device, context, |command_buffer| {
+        let subresource = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let barrier = vk::ImageMemoryBarrier::builder()
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource)
+            .src_access_mask(src_access_mask)
+            .dst_access_mask(dst_access_mask);
+
+        device.vk_device.cmd_pipeline_barrier(
+            command_buffer,
+            src_stage,
+            dst_stage,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+    })
+}
+
+pub unsafe fn copy_buffer_to_image(
+    device: &VulkanDevice,
+    context: &VulkanContext,
+    buffer: vk::Buffer,
+    image: vk::Image,
+    width: u32,
+    height: u32,
+) -> Result<()> {
+    super::buffer::execute_one_time_commands(device, context, |command_buffer| {
+        let subresource = vk::ImageSubresourceLayers::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .mip_level(0)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let region = vk::BufferImageCopy::builder()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(subresource)
+            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+            .image_extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            });
+
+        device.vk_device.cmd_copy_buffer_to_image(
+            command_buffer,
+            buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+    })
+}
 
 #[derive(Debug)]
-pub struct VulkanImage;
+pub struct VulkanTexture;
+
+impl VulkanTexture {
+    pub unsafe fn create(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let image = image::load_from_memory(TEXTURE_BYTES)?.into_rgba8();
+        let (width, height) = image.dimensions();
+        let pixels = image.into_raw();
+        let size = pixels.len() as u64;
+
+        let (staging_buffer, staging_buffer_memory) = create_buffer(
+            instance,
+            device,
+            context,
+            size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        let memory =
+            device
+                .vk_device
+                .map_memory(staging_buffer_memory, 0, size, vk::MemoryMapFlags::empty())?;
+        memcpy(pixels.as_ptr(), memory.cast(), pixels.len());
+        device.vk_device.unmap_memory(staging_buffer_memory);
+
+        let (texture_image, texture_image_memory) = create_image(
+            instance,
+            device,
+            context,
+            width,
+            height,
+            vk::Format::R8G8B8A8_SRGB,
+            vk::ImageTiling::OPTIMAL,
+            vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+
+        transition_image_layout(
+            device,
+            context,
+            texture_image,
+            vk::ImageLayout::UNDEFINED,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+        )?;
+        copy_buffer_to_image(device, context, staging_buffer, texture_image, width, height)?;
+        transition_image_layout(
+            device,
+            context,
+            texture_image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        )?;
+
+        device.vk_device.destroy_buffer(staging_buffer, None);
+        device.vk_device.free_memory(staging_buffer_memory, None);
+
+        let texture_image_view = create_image_view(
+            device,
+            texture_image,
+            vk::Format::R8G8B8A8_SRGB,
+            vk::ImageAspectFlags::COLOR,
+        )?;
+
+        let sampler_info = vk::SamplerCreateInfo::builder()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::REPEAT)
+            .address_mode_v(vk::SamplerAddressMode::REPEAT)
+            .address_mode_w(vk::SamplerAddressMode::REPEAT)
+            .anisotropy_enable(false)
+            .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::LINEAR);
+
+        let texture_sampler = device.vk_device.create_sampler(&sampler_info, None)?;
+
+        context.texture_image = texture_image;
+        context.texture_image_memory = texture_image_memory;
+        context.texture_image_view = texture_image_view;
+        context.texture_sampler = texture_sampler;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct VulkanDepthResources;
+
+impl VulkanDepthResources {
+    pub unsafe fn create(
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let format = find_depth_format(instance, context)?;
+
+        let (depth_image, depth_image_memory) = create_image(
+            instance,
+            device,
+            context,
+            context.swapchain_extent.width,
+            context.swapchain_extent.height,
+            format,
+            vk::ImageTiling::OPTIMAL,
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+
+        let depth_image_view =
+            create_image_view(device, depth_image, format, vk::ImageAspectFlags::DEPTH)?;
+
+        context.depth_format = format;
+        context.depth_image = depth_image;
+        context.depth_image_memory = depth_image_memory;
+        context.depth_image_view = depth_image_view;
 
-impl VulkanImage {}
+        Ok(())
+    }
+}
@@ -1,10 +1,19 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
 use anyhow::{anyhow, Result};
+use buffer::{VulkanIndexBuffer, VulkanVertexBuffer};
 use command_buffer::VulkanCommandBuffer;
+use compute::VulkanComputePipeline;
 use context::VulkanContext;
+use descriptor::VulkanDescriptor;
 use device::VulkanDevice;
 use framebuffer::VulkanFramebuffer;
+use image::{VulkanDepthResources, VulkanTexture};
 use instance::VulkanInstance;
+use log::info;
 use pipeline::VulkanPipeline;
+use shader::ShaderWatcher;
 use swapchain::VulkanSwapchain;
 use vulkanalia::{
     loader::{LibloadingLoader, LIBRARY},
@@ -13,15 +22,19 @@ use vulkanalia::{
 };
 use winit::window::Window;
 
+mod buffer;
 mod command_buffer;
+mod compute;
 mod constants;
 mod context;
+mod descriptor;
 mod device;
 mod framebuffer;
 mod image;
 mod instance;
 mod pipeline;
 mod render_pass;
+mod shader;
 mod swapchain;
 
 #[derive(Debug)]
@@ -31,6 +44,11 @@ pub struct VulkanRenderer {
     pub pipeline: VulkanPipeline,
     context: VulkanContext,
     frame: usize,
+    start: Instant,
+    /// Set by the window's `Resized` event; forces a swapchain rebuild on the next frame.
+    resized: bool,
+    /// Watches the GLSL sources for live edits when `shader::RUNTIME_SHADER_COMPILATION` is on.
+    shader_watcher: Option<ShaderWatcher>,
 }
 
 impl VulkanRenderer {
@@ -46,22 +64,51 @@ impl VulkanRenderer {
         VulkanSwapchain::create(window, &instance, &device, &mut context)?;
         VulkanSwapchain::create_image_views(&device, &mut context)?;
 
+        VulkanDescriptor::create_descriptor_set_layout(&device, &mut context)?;
         let pipeline = VulkanPipeline::create(&instance, &device, &mut context)?;
+        VulkanDepthResources::create(&instance, &device, &mut context)?;
         VulkanFramebuffer::create(&device, &mut context)?;
         VulkanCommandBuffer::create_command_pool(&instance, &device, &mut context)?;
+        VulkanCommandBuffer::create_transfer_command_pool(&instance, &device, &mut context)?;
+        VulkanVertexBuffer::create(&instance, &device, &mut context)?;
+        VulkanIndexBuffer::create(&instance, &device, &mut context)?;
+        VulkanTexture::create(&instance, &device, &mut context)?;
+        VulkanDescriptor::create_uniform_buffers(&instance, &device, &mut context)?;
+        VulkanDescriptor::create_descriptor_pool(&device, &mut context)?;
+        VulkanDescriptor::create_descriptor_sets(&device, &mut context)?;
+        VulkanComputePipeline::create(&instance, &device, &mut context)?;
+        VulkanComputePipeline::create_command_pool(&instance, &device, &mut context)?;
+        VulkanComputePipeline::create_command_buffers(&device, &mut context)?;
         VulkanCommandBuffer::create_command_buffers(&device, &mut context)?;
 
         VulkanRenderer::create_sync_objects(&device, &mut context)?;
 
+        let shader_watcher = if shader::RUNTIME_SHADER_COMPILATION {
+            Some(ShaderWatcher::new(&[
+                PathBuf::from(shader::VERTEX_SHADER_PATH),
+                PathBuf::from(shader::FRAGMENT_SHADER_PATH),
+            ])?)
+        } else {
+            None
+        };
+
         Ok(VulkanRenderer {
             instance,
             device,
             pipeline,
             context,
             frame: 0,
+            start: Instant::now(),
+            resized: false,
+            shader_watcher,
         })
     }
 
+    /// Marks the swapchain for recreation on the next call to [`VulkanRenderer::render`].
+    pub fn resize(&mut self) {
+        self.resized = true;
+    }
+
     unsafe fn create_sync_objects(
         device: &VulkanDevice,
         context: &mut VulkanContext,
@@ -79,6 +126,9 @@ impl VulkanRenderer {
             context
                 .in_flight_fences
                 .push(device.vk_device.create_fence(&fence_info, None)?);
+            context
+                .compute_finished_semaphores
+                .push(device.vk_device.create_semaphore(&semaphore_info, None)?);
         }
 
         context.images_in_flight = context
@@ -90,23 +140,41 @@ impl VulkanRenderer {
         Ok(())
     }
 
-    pub unsafe fn render(&mut self) -> Result<()> {
+    pub unsafe fn render(&mut self, window: &Window) -> Result<()> {
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            // Minimized: there is no framebuffer to render into.
+            return Ok(());
+        }
+
+        if let Some(watcher) = &self.shader_watcher {
+            if watcher.poll_changed().is_some() {
+                info!("Shader source changed, rebuilding the graphics pipeline.");
+                self.device_wait_idle();
+                self.recreate_swapchain(window)?;
+            }
+        }
+
         self.device.vk_device.wait_for_fences(
             &[self.context.in_flight_fences[self.frame]],
             true,
             u64::MAX,
         )?;
 
-        let image_index = self
-            .device
-            .vk_device
-            .acquire_next_image_khr(
-                self.context.swapchain,
-                u64::MAX,
-                self.context.image_available_semaphores[self.frame],
-                vk::Fence::null(),
-            )?
-            .0 as usize;
+        let result = self.device.vk_device.acquire_next_image_khr(
+            self.context.swapchain,
+            u64::MAX,
+            self.context.image_available_semaphores[self.frame],
+            vk::Fence::null(),
+        );
+
+        let image_index = match result {
+            Ok((image_index, _)) => image_index as usize,
+            Err(vk::ErrorCode::OUT_OF_DATE_KHR) => {
+                return self.recreate_swapchain(window);
+            }
+            Err(e) => return Err(anyhow!(e)),
+        };
 
         if !self.context.images_in_flight[image_index as usize].is_null() {
             self.device.vk_device.wait_for_fences(
@@ -119,8 +187,30 @@ impl VulkanRenderer {
         self.context.images_in_flight[image_index as usize] =
             self.context.in_flight_fences[self.frame];
 
-        let wait_semaphores = &[self.context.image_available_semaphores[self.frame]];
-        let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        VulkanDescriptor::update_uniform_buffer(
+            &self.device,
+            &self.context,
+            image_index,
+            self.start,
+        )?;
+
+        VulkanComputePipeline::submit(&self.device, &self.context, self.frame)?;
+        let particle_buffer = (self.frame % 2) ^ 1;
+        VulkanCommandBuffer::record_command_buffer(
+            &self.device,
+            &self.context,
+            image_index,
+            particle_buffer,
+        )?;
+
+        let wait_semaphores = &[
+            self.context.image_available_semaphores[self.frame],
+            self.context.compute_finished_semaphores[self.frame],
+        ];
+        let wait_stages = &[
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+            vk::PipelineStageFlags::VERTEX_INPUT,
+        ];
         let command_buffers = &[self.context.command_buffers[image_index as usize]];
         let signal_semaphores = &[self.context.render_finished_semaphores[self.frame]];
         let submit_info = vk::SubmitInfo::builder()
@@ -146,19 +236,135 @@ impl VulkanRenderer {
             .swapchains(swapchains)
             .image_indices(image_indices);
 
-        self.device
-            .vk_device
-            .queue_present_khr(self.context.present_queue, &present_info)?;
-        self.device
+        let result = self
+            .device
             .vk_device
-            .queue_wait_idle(self.context.present_queue)?;
+            .queue_present_khr(self.context.present_queue, &present_info);
+
+        let changed = matches!(
+            result,
+            Err(vk::ErrorCode::OUT_OF_DATE_KHR) | Ok(vk::SuccessCode::SUBOPTIMAL_KHR)
+        );
 
         self.frame = (self.frame + 1) % constants::MAX_FRAMES_IN_FLIGHT;
 
+        if changed || self.resized {
+            self.resized = false;
+            self.recreate_swapchain(window)?;
+        } else if let Err(e) = result {
+            return Err(anyhow!(e));
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds every swapchain-dependent object against the window's current extent.
+    unsafe fn recreate_swapchain(&mut self, window: &Window) -> Result<()> {
+        self.device_wait_idle();
+        self.destroy_swapchain();
+
+        VulkanSwapchain::create(window, &self.instance, &self.device, &mut self.context)?;
+        VulkanSwapchain::create_image_views(&self.device, &mut self.context)?;
+        self.pipeline = VulkanPipeline::create(&self.instance, &self.device, &mut self.context)?;
+        VulkanDepthResources::create(&self.instance, &self.device, &mut self.context)?;
+        VulkanFramebuffer::create(&self.device, &mut self.context)?;
+        VulkanCommandBuffer::create_command_buffers(&self.device, &mut self.context)?;
+
+        self.context
+            .images_in_flight
+            .resize(self.context.swapchain_images.len(), vk::Fence::null());
+
         Ok(())
     }
 
+    /// Tears down everything `recreate_swapchain` rebuilds, leaving the command pool intact.
+    unsafe fn destroy_swapchain(&mut self) {
+        self.device
+            .vk_device
+            .destroy_image_view(self.context.depth_image_view, None);
+        self.device
+            .vk_device
+            .destroy_image(self.context.depth_image, None);
+        self.device
+            .vk_device
+            .free_memory(self.context.depth_image_memory, None);
+        self.context
+            .framebuffers
+            .iter()
+            .for_each(|f| self.device.vk_device.destroy_framebuffer(*f, None));
+        self.device
+            .vk_device
+            .free_command_buffers(self.context.command_pool, &self.context.command_buffers);
+        self.device
+            .vk_device
+            .destroy_pipeline(self.context.particle_pipeline, None);
+        self.device
+            .vk_device
+            .destroy_pipeline_layout(self.context.particle_pipeline_layout, None);
+        self.device.vk_device.destroy_pipeline(self.context.pipeline, None);
+        self.device
+            .vk_device
+            .destroy_pipeline_layout(self.context.pipeline_layout, None);
+        self.device
+            .vk_device
+            .destroy_render_pass(self.context.render_pass, None);
+        self.context
+            .swapchain_image_views
+            .iter()
+            .for_each(|v| self.device.vk_device.destroy_image_view(*v, None));
+        self.device
+            .vk_device
+            .destroy_swapchain_khr(self.context.swapchain, None);
+    }
+
     pub unsafe fn destroy(&mut self) {
+        self.device
+            .vk_device
+            .destroy_sampler(self.context.texture_sampler, None);
+        self.device
+            .vk_device
+            .destroy_image_view(self.context.texture_image_view, None);
+        self.device
+            .vk_device
+            .destroy_image(self.context.texture_image, None);
+        self.device
+            .vk_device
+            .free_memory(self.context.texture_image_memory, None);
+        self.device
+            .vk_device
+            .destroy_image_view(self.context.depth_image_view, None);
+        self.device
+            .vk_device
+            .destroy_image(self.context.depth_image, None);
+        self.device
+            .vk_device
+            .free_memory(self.context.depth_image_memory, None);
+        self.device
+            .vk_device
+            .destroy_descriptor_pool(self.context.descriptor_pool, None);
+        self.context
+            .uniform_buffers
+            .iter()
+            .for_each(|b| self.device.vk_device.destroy_buffer(*b, None));
+        self.context
+            .uniform_buffers_memory
+            .iter()
+            .for_each(|m| self.device.vk_device.free_memory(*m, None));
+        self.device
+            .vk_device
+            .destroy_descriptor_set_layout(self.context.descriptor_set_layout, None);
+        self.device
+            .vk_device
+            .destroy_buffer(self.context.index_buffer, None);
+        self.device
+            .vk_device
+            .free_memory(self.context.index_buffer_memory, None);
+        self.device
+            .vk_device
+            .destroy_buffer(self.context.vertex_buffer, None);
+        self.device
+            .vk_device
+            .free_memory(self.context.vertex_buffer_memory, None);
         self.context
             .in_flight_fences
             .iter()
@@ -171,14 +377,16 @@ impl VulkanRenderer {
             .image_available_semaphores
             .iter()
             .for_each(|s| self.device.vk_device.destroy_semaphore(*s, None));
+        self.context
+            .compute_finished_semaphores
+            .iter()
+            .for_each(|s| self.device.vk_device.destroy_semaphore(*s, None));
         self.device
             .vk_device
-            .destroy_semaphore(self.context.render_finished_semaphore, None);
-        self.device
-            .vk_device
-            .destroy_semaphore(self.context.image_available_semaphore, None);
+            .destroy_command_pool(self.context.transfer_command_pool, None);
+        VulkanComputePipeline::destroy(&self.device, &mut self.context);
         self.pipeline.destroy(&self.device, &mut self.context);
-        self.device.destroy(&mut self.context);
+        self.device.destroy(&self.instance, &mut self.context);
         self.instance.destroy(&mut self.context);
     }
 
@@ -1,5 +1,10 @@
+use std::path::Path;
+
+use super::buffer::Vertex;
+use super::compute::Particle;
 use super::instance::VulkanInstance;
 use super::render_pass::{self, VulkanRenderPass};
+use super::shader::{self, ShaderStage};
 use super::{context::VulkanContext, device::VulkanDevice};
 use anyhow::{Ok, Result};
 use vulkanalia::bytecode::Bytecode;
@@ -17,13 +22,30 @@ impl VulkanPipeline {
         context: &mut VulkanContext,
     ) -> Result<VulkanPipeline> {
         // Render pass
-        let render_pass = VulkanRenderPass::create(device, context)?;
-
-        let vert = include_bytes!("../../../shaders/vert.spv");
-        let frag = include_bytes!("../../../shaders/frag.spv");
+        let render_pass = VulkanRenderPass::create(instance, device, context)?;
 
-        let vertex_shader_module = VulkanPipeline::create_shader_module(device, &vert[..])?;
-        let fragment_shader_module = VulkanPipeline::create_shader_module(device, &frag[..])?;
+        let (vertex_shader_module, fragment_shader_module) = if shader::RUNTIME_SHADER_COMPILATION
+        {
+            let vert = shader::compile_shader(
+                Path::new(shader::VERTEX_SHADER_PATH),
+                ShaderStage::Vertex,
+            )?;
+            let frag = shader::compile_shader(
+                Path::new(shader::FRAGMENT_SHADER_PATH),
+                ShaderStage::Fragment,
+            )?;
+            (
+                VulkanPipeline::create_shader_module_from_words(device, &vert)?,
+                VulkanPipeline::create_shader_module_from_words(device, &frag)?,
+            )
+        } else {
+            let vert = include_bytes!("../../../shaders/vert.spv");
+            let frag = include_bytes!("../../../shaders/frag.spv");
+            (
+                VulkanPipeline::create_shader_module(device, &vert[..])?,
+                VulkanPipeline::create_shader_module(device, &frag[..])?,
+            )
+        };
 
         let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
             .stage(vk::ShaderStageFlags::VERTEX)
@@ -35,7 +57,11 @@ impl VulkanPipeline {
             .module(fragment_shader_module)
             .name(b"main\0");
 
-        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder();
+        let binding_descriptions = &[Vertex::binding_description()];
+        let attribute_descriptions = Vertex::attribute_descriptions();
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(binding_descriptions)
+            .vertex_attribute_descriptions(&attribute_descriptions);
         let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
             .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
             .primitive_restart_enable(false);
@@ -91,8 +117,17 @@ impl VulkanPipeline {
             .attachments(attachments)
             .blend_constants([0.0, 0.0, 0.0, 0.0]);
 
+        // depth/stencil
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+            .depth_test_enable(true)
+            .depth_write_enable(true)
+            .depth_compare_op(vk::CompareOp::LESS)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
         // layout
-        let layout_info = vk::PipelineLayoutCreateInfo::builder();
+        let set_layouts = &[context.descriptor_set_layout];
+        let layout_info = vk::PipelineLayoutCreateInfo::builder().set_layouts(set_layouts);
         context.pipeline_layout = device
             .vk_device
             .create_pipeline_layout(&layout_info, None)?;
@@ -105,6 +140,7 @@ impl VulkanPipeline {
             .viewport_state(&viewport_state)
             .rasterization_state(&rasterization_state)
             .multisample_state(&multisample_state)
+            .depth_stencil_state(&depth_stencil_state)
             .color_blend_state(&color_blend_state)
             .layout(context.pipeline_layout)
             .render_pass(context.render_pass)
@@ -123,9 +159,127 @@ impl VulkanPipeline {
             .vk_device
             .destroy_shader_module(fragment_shader_module, None);
 
+        VulkanPipeline::create_particle_pipeline(device, context)?;
+
         Ok(VulkanPipeline { render_pass })
     }
 
+    /// Builds the point-list pipeline that renders the particles written by the compute pass.
+    unsafe fn create_particle_pipeline(device: &VulkanDevice, context: &mut VulkanContext) -> Result<()> {
+        let vert = include_bytes!("../../../shaders/point.vert.spv");
+        let frag = include_bytes!("../../../shaders/point.frag.spv");
+
+        let vertex_shader_module = VulkanPipeline::create_shader_module(device, &vert[..])?;
+        let fragment_shader_module = VulkanPipeline::create_shader_module(device, &frag[..])?;
+
+        let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vertex_shader_module)
+            .name(b"main\0");
+
+        let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(fragment_shader_module)
+            .name(b"main\0");
+
+        let binding_descriptions = &[Particle::binding_description()];
+        let attribute_descriptions = Particle::attribute_descriptions();
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(binding_descriptions)
+            .vertex_attribute_descriptions(&attribute_descriptions);
+        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(vk::PrimitiveTopology::POINT_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(context.swapchain_extent.width as f32)
+            .height(context.swapchain_extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+
+        let scissor = vk::Rect2D::builder()
+            .offset(vk::Offset2D { x: 0, y: 0 })
+            .extent(context.swapchain_extent);
+
+        let viewports = &[viewport];
+        let scissors = &[scissor];
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewports(viewports)
+            .scissors(scissors);
+
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::_1);
+
+        let attachment = vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE)
+            .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let attachments = &[attachment];
+        let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+            .logic_op_enable(false)
+            .logic_op(vk::LogicOp::COPY)
+            .attachments(attachments)
+            .blend_constants([0.0, 0.0, 0.0, 0.0]);
+
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+            .depth_test_enable(true)
+            .depth_write_enable(true)
+            .depth_compare_op(vk::CompareOp::LESS)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        let layout_info = vk::PipelineLayoutCreateInfo::builder();
+        context.particle_pipeline_layout = device
+            .vk_device
+            .create_pipeline_layout(&layout_info, None)?;
+
+        let stages = &[vert_stage, frag_stage];
+        let info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(stages)
+            .vertex_input_state(&vertex_input_state)
+            .input_assembly_state(&input_assembly_state)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization_state)
+            .multisample_state(&multisample_state)
+            .depth_stencil_state(&depth_stencil_state)
+            .color_blend_state(&color_blend_state)
+            .layout(context.particle_pipeline_layout)
+            .render_pass(context.render_pass)
+            .subpass(0);
+
+        context.particle_pipeline = device
+            .vk_device
+            .create_graphics_pipelines(vk::PipelineCache::null(), &[info], None)?
+            .0[0];
+
+        device
+            .vk_device
+            .destroy_shader_module(vertex_shader_module, None);
+        device
+            .vk_device
+            .destroy_shader_module(fragment_shader_module, None);
+
+        Ok(())
+    }
+
     unsafe fn create_shader_module(
         device: &VulkanDevice,
         bytecode: &[u8],
@@ -138,6 +292,19 @@ impl VulkanPipeline {
         Ok(device.vk_device.create_shader_module(&info, None)?)
     }
 
+    /// Builds a shader module from SPIR-V words produced at runtime by `shaderc`, rather than
+    /// from precompiled bytes embedded via `include_bytes!`.
+    unsafe fn create_shader_module_from_words(
+        device: &VulkanDevice,
+        code: &[u32],
+    ) -> Result<vk::ShaderModule> {
+        let info = vk::ShaderModuleCreateInfo::builder()
+            .code_size(code.len() * 4)
+            .code(code);
+
+        Ok(device.vk_device.create_shader_module(&info, None)?)
+    }
+
     pub unsafe fn destroy(&mut self, device: &VulkanDevice, context: &mut VulkanContext) {
         device
             .vk_device
@@ -146,6 +313,12 @@ impl VulkanPipeline {
             .framebuffers
             .iter()
             .for_each(|f| device.vk_device.destroy_framebuffer(*f, None));
+        device
+            .vk_device
+            .destroy_pipeline(context.particle_pipeline, None);
+        device
+            .vk_device
+            .destroy_pipeline_layout(context.particle_pipeline_layout, None);
         device.vk_device.destroy_pipeline(context.pipeline, None);
         device
             .vk_device
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+use anyhow::{anyhow, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use shaderc::{Compiler, ShaderKind};
+
+/// Whether shaders are compiled from GLSL source at runtime via `shaderc`. When `false`,
+/// `VulkanPipeline` falls back to the precompiled `.spv` files `build.rs` produces ahead of time.
+pub const RUNTIME_SHADER_COMPILATION: bool = cfg!(feature = "runtime-shaders");
+
+/// The GLSL sources `VulkanPipeline` compiles and `ShaderWatcher` watches in the
+/// `runtime-shaders` configuration. Kept in one place so the two can't drift apart.
+pub const VERTEX_SHADER_PATH: &str = "shaders/shader.vert";
+pub const FRAGMENT_SHADER_PATH: &str = "shaders/shader.frag";
+
+#[derive(Copy, Clone, Debug)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Compute,
+}
+
+impl ShaderStage {
+    fn kind(self) -> ShaderKind {
+        match self {
+            ShaderStage::Vertex => ShaderKind::Vertex,
+            ShaderStage::Fragment => ShaderKind::Fragment,
+            ShaderStage::Compute => ShaderKind::Compute,
+        }
+    }
+}
+
+/// Compiles the GLSL source at `path` to SPIR-V words, suitable for
+/// `vk::ShaderModuleCreateInfo::code`.
+pub fn compile_shader(path: &Path, stage: ShaderStage) -> Result<Vec<u32>> {
+    let source = std::fs::read_to_string(path)?;
+    let file_name = path.to_string_lossy();
+
+    let compiler = Compiler::new().ok_or_else(|| anyhow!("Failed to create shaderc compiler."))?;
+    let artifact =
+        compiler.compile_into_spirv(&source, stage.kind(), &file_name, "main", None)?;
+
+    Ok(artifact.as_binary().to_vec())
+}
+
+/// Watches a fixed set of shader source files and reports the ones that changed since the last
+/// poll, so the pipeline can recompile and rebuild without restarting the app.
+pub struct ShaderWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+}
+
+impl ShaderWatcher {
+    pub fn new(paths: &[PathBuf]) -> Result<Self> {
+        let (tx, events) = channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        for path in paths {
+            watcher.watch(path, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Returns the next changed path reported by the filesystem watcher, if any, without
+    /// blocking the caller.
+    pub fn poll_changed(&self) -> Option<PathBuf> {
+        self.events
+            .try_iter()
+            .filter_map(|event| event.ok())
+            .flat_map(|event| event.paths)
+            .next()
+    }
+}
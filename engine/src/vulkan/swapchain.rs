@@ -0,0 +1,165 @@
+use anyhow::{Ok, Result};
+use vulkanalia::vk::{self, DeviceV1_0, HasBuilder, InstanceV1_0, KhrSurfaceExtension, KhrSwapchainExtension};
+use vulkanalia::window as vk_window;
+use winit::window::Window;
+
+use super::{
+    context::VulkanContext,
+    device::{QueueFamilyIndices, VulkanDevice},
+    image::create_image_view,
+    instance::VulkanInstance,
+};
+
+/// The swapchain support queried from a physical device/surface pair.
+#[derive(Clone, Debug)]
+pub struct SwapchainSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKHR,
+    pub formats: Vec<vk::SurfaceFormatKHR>,
+    pub present_modes: Vec<vk::PresentModeKHR>,
+}
+
+impl SwapchainSupport {
+    unsafe fn get(
+        instance: &VulkanInstance,
+        context: &VulkanContext,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        Ok(Self {
+            capabilities: instance
+                .vk_instance
+                .get_physical_device_surface_capabilities_khr(physical_device, context.surface)?,
+            formats: instance
+                .vk_instance
+                .get_physical_device_surface_formats_khr(physical_device, context.surface)?,
+            present_modes: instance
+                .vk_instance
+                .get_physical_device_surface_present_modes_khr(physical_device, context.surface)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct VulkanSwapchain;
+
+impl VulkanSwapchain {
+    pub unsafe fn get(
+        instance: &VulkanInstance,
+        context: &VulkanContext,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<SwapchainSupport> {
+        SwapchainSupport::get(instance, context, physical_device)
+    }
+
+    /// Creates the window surface that every later swapchain depends on.
+    pub unsafe fn new(
+        window: &Window,
+        instance: &VulkanInstance,
+        context: &mut VulkanContext,
+    ) -> Result<VulkanSwapchain> {
+        context.surface = vk_window::create_surface(&instance.vk_instance, window, window)?;
+        Ok(VulkanSwapchain)
+    }
+
+    pub unsafe fn create(
+        window: &Window,
+        instance: &VulkanInstance,
+        device: &VulkanDevice,
+        context: &mut VulkanContext,
+    ) -> Result<()> {
+        let indices = QueueFamilyIndices::get(instance, context, context.physical_device)?;
+        let support = SwapchainSupport::get(instance, context, context.physical_device)?;
+
+        let surface_format = Self::get_swapchain_surface_format(&support.formats);
+        let present_mode = Self::get_swapchain_present_mode(&support.present_modes);
+        let extent = Self::get_swapchain_extent(window, support.capabilities);
+
+        let mut image_count = support.capabilities.min_image_count + 1;
+        if support.capabilities.max_image_count != 0
+            && image_count > support.capabilities.max_image_count
+        {
+            image_count = support.capabilities.max_image_count;
+        }
+
+        let mut queue_family_indices = vec![];
+        let image_sharing_mode = if indices.graphics != indices.present {
+            queue_family_indices.push(indices.graphics);
+            queue_family_indices.push(indices.present);
+            vk::SharingMode::CONCURRENT
+        } else {
+            vk::SharingMode::EXCLUSIVE
+        };
+
+        let info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(context.surface)
+            .min_image_count(image_count)
+            .image_format(surface_format.format)
+            .image_color_space(surface_format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_sharing_mode(image_sharing_mode)
+            .queue_family_indices(&queue_family_indices)
+            .pre_transform(support.capabilities.current_transform)
+            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(vk::SwapchainKHR::null());
+
+        context.swapchain = device.vk_device.create_swapchain_khr(&info, None)?;
+        context.swapchain_images = device
+            .vk_device
+            .get_swapchain_images_khr(context.swapchain)?;
+        context.swapchain_format = surface_format.format;
+        context.swapchain_extent = extent;
+
+        Ok(())
+    }
+
+    pub unsafe fn create_image_views(device: &VulkanDevice, context: &mut VulkanContext) -> Result<()> {
+        context.swapchain_image_views = context
+            .swapchain_images
+            .iter()
+            .map(|i| create_image_view(device, *i, context.swapchain_format, vk::ImageAspectFlags::COLOR))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(())
+    }
+
+    fn get_swapchain_surface_format(formats: &[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR {
+        formats
+            .iter()
+            .find(|f| {
+                f.format == vk::Format::B8G8R8A8_SRGB
+                    && f.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
+            })
+            .copied()
+            .unwrap_or_else(|| formats[0])
+    }
+
+    fn get_swapchain_present_mode(present_modes: &[vk::PresentModeKHR]) -> vk::PresentModeKHR {
+        present_modes
+            .iter()
+            .find(|m| **m == vk::PresentModeKHR::MAILBOX)
+            .copied()
+            .unwrap_or(vk::PresentModeKHR::FIFO)
+    }
+
+    /// Clamps the window's current size to the surface's `current_extent`/min/max image extent.
+    fn get_swapchain_extent(window: &Window, capabilities: vk::SurfaceCapabilitiesKHR) -> vk::Extent2D {
+        if capabilities.current_extent.width != u32::MAX {
+            capabilities.current_extent
+        } else {
+            let size = window.inner_size();
+            vk::Extent2D::builder()
+                .width(size.width.clamp(
+                    capabilities.min_image_extent.width,
+                    capabilities.max_image_extent.width,
+                ))
+                .height(size.height.clamp(
+                    capabilities.min_image_extent.height,
+                    capabilities.max_image_extent.height,
+                ))
+                .build()
+        }
+    }
+}